@@ -30,6 +30,12 @@
 //! (4, 3)
 //! (5, 3)
 //! ```
+//!
+//! Besides the exact integer-traversal `Bresenham`, this crate also has
+//! [`Midpoint`] for floating-point endpoints, [`Vector`] for unit-vector
+//! stepping, and [`BresenhamInclusive`] for a line that includes its `end`
+//! point. Use [`line`] with a [`LineAlg`] to pick between `Bresenham` and
+//! `Vector` behind one call site.
 
 #![no_std]
 
@@ -37,18 +43,79 @@
 extern crate std;
 
 use core::iter::Iterator;
+use core::ops::{Add, Neg, Sub};
+
+/// Coordinate types usable with `Bresenham`.
+///
+/// Any signed integer type that supports the arithmetic the octant
+/// transforms and error-term recurrence need (addition, subtraction,
+/// negation, ordering, and conversion from the small constants `-1`, `0`
+/// and `1`) can be plugged in, so callers can draw lines directly in
+/// whatever width their framebuffer or grid uses instead of casting
+/// through `isize`.
+///
+/// The octant transform negates coordinates and deltas, so `T::MIN` (e.g.
+/// `i8::MIN`) is not a safe coordinate or delta to draw with: negating it
+/// overflows, the same caveat that applies to `T::MIN.abs()` elsewhere in
+/// Rust. Stick to the inner range of `T` on narrow types.
+pub trait SignedNum:
+    Copy + PartialOrd + Add<Output = Self> + Sub<Output = Self> + Neg<Output = Self> + From<i8>
+{
+}
+
+/// Rounds a float to the nearest integer, away from zero on ties.
+///
+/// `f64::round` lives in `std`, not `core`, so `no_std` callers of
+/// `Midpoint` need this instead.
+#[inline]
+fn round(x: f64) -> f64 {
+    (x + if x >= 0.0 { 0.5 } else { -0.5 }) as i64 as f64
+}
+
+/// Computes a square root via Newton's method.
+///
+/// `f64::sqrt` is also `std`-only, and pulling in `libm` just for `Vector`
+/// would add a dependency for one call per line. Starting from a guess far
+/// from the root, each Newton step roughly halves the error until it gets
+/// close, and only then converges quadratically; the iteration bound covers
+/// that halving phase for any finite positive `x`, so the loop reaches `f64`
+/// precision and normally exits early.
+#[inline]
+fn sqrt(x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+
+    let mut guess = x;
+    for _ in 0..1100 {
+        let next = 0.5 * (guess + x / guess);
+        if (next - guess).abs() <= f64::EPSILON * next {
+            return next;
+        }
+        guess = next;
+    }
+    guess
+}
+
+impl<T> SignedNum for T where
+    T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T> + Neg<Output = T> + From<i8>
+{
+}
 
-/// Convenient typedef for two machines-sized integers
+/// Convenient typedef for two machine-sized integers
 pub type Point = (isize, isize);
 
 /// Line-drawing iterator
-pub struct Bresenham {
-    x: isize,
-    y: isize,
-    dx: isize,
-    dy: isize,
-    x1: isize,
-    diff: isize,
+pub struct Bresenham<T = isize> {
+    x: T,
+    y: T,
+    dx: T,
+    dy: T,
+    diff: T,
+    // Cursor walking backwards from `end`, used to support `DoubleEndedIterator`.
+    x_back: T,
+    y_back: T,
+    diff_back: T,
     octant: Octant,
 }
 
@@ -57,19 +124,19 @@ struct Octant(u8);
 impl Octant {
     /// adapted from http://codereview.stackexchange.com/a/95551
     #[inline]
-    fn from_points(start: Point, end: Point) -> Octant {
+    fn from_points<T: SignedNum>(start: (T, T), end: (T, T)) -> Octant {
         let mut dx = end.0 - start.0;
         let mut dy = end.1 - start.1;
 
         let mut octant = 0;
 
-        if dy < 0 {
+        if dy < T::from(0) {
             dx = -dx;
             dy = -dy;
             octant += 4;
         }
 
-        if dx < 0 {
+        if dx < T::from(0) {
             let tmp = dx;
             dx = dy;
             dy = -tmp;
@@ -84,7 +151,7 @@ impl Octant {
     }
 
     #[inline]
-    fn to_octant0(&self, p: Point) -> Point {
+    fn to_octant0<T: SignedNum>(&self, p: (T, T)) -> (T, T) {
         match self.0 {
             0 => (p.0, p.1),
             1 => (p.1, p.0),
@@ -99,7 +166,7 @@ impl Octant {
     }
 
     #[inline]
-    fn from_octant0(&self, p: Point) -> Point {
+    fn from_octant0<T: SignedNum>(&self, p: (T, T)) -> (T, T) {
         match self.0 {
             0 => (p.0, p.1),
             1 => (p.1, p.0),
@@ -114,11 +181,15 @@ impl Octant {
     }
 }
 
-impl Bresenham {
+impl<T: SignedNum> Bresenham<T> {
     /// Creates a new iterator.Yields intermediate points between `start`
     /// and `end`. Does include `start` but not `end`.
+    ///
+    /// See the [`SignedNum`] caveat about `T::MIN` coordinates on narrow
+    /// integer types: folding `start`/`end` into the first octant can
+    /// negate them, which overflows at that boundary.
     #[inline]
-    pub fn new(start: Point, end: Point) -> Bresenham {
+    pub fn new(start: (T, T), end: (T, T)) -> Bresenham<T> {
         let octant = Octant::from_points(start, end);
 
         let start = octant.to_octant0(start);
@@ -132,37 +203,61 @@ impl Bresenham {
             y: start.1,
             dx,
             dy,
-            x1: end.0,
             diff: dy - dx,
+            x_back: end.0,
+            y_back: end.1,
+            diff_back: T::from(0),
             octant,
         }
     }
 
     /// Return the next point without checking if we are past `end`.
     #[inline]
-    pub fn advance(&mut self) -> Point {
+    pub fn advance(&mut self) -> (T, T) {
         let p = (self.x, self.y);
 
-        if self.diff >= 0 {
-            self.y += 1;
-            self.diff -= self.dx;
+        if self.diff >= T::from(0) {
+            self.y = self.y + T::from(1);
+            self.diff = self.diff - self.dx;
         }
 
-        self.diff += self.dy;
+        self.diff = self.diff + self.dy;
 
         // loop inc
-        self.x += 1;
+        self.x = self.x + T::from(1);
 
         self.octant.from_octant0(p)
     }
+
+    /// Return the previous point (walking in from `end`) without checking
+    /// if we have passed the front cursor.
+    #[inline]
+    pub fn advance_back(&mut self) -> (T, T) {
+        self.x_back = self.x_back - T::from(1);
+        self.diff_back = self.diff_back + self.dy;
+
+        if self.diff_back > T::from(0) {
+            self.y_back = self.y_back - T::from(1);
+            self.diff_back = self.diff_back - self.dx;
+        }
+
+        self.octant.from_octant0((self.x_back, self.y_back))
+    }
+
+    /// Adapts this iterator to yield `(point, next_point)` pairs instead
+    /// of individual points. See [`Steps`].
+    #[inline]
+    pub fn steps(self) -> Steps<Self> {
+        Steps::new(self)
+    }
 }
 
-impl Iterator for Bresenham {
-    type Item = Point;
+impl<T: SignedNum> Iterator for Bresenham<T> {
+    type Item = (T, T);
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        if self.x >= self.x1 {
+        if self.x >= self.x_back {
             None
         } else {
             Some(self.advance())
@@ -170,34 +265,308 @@ impl Iterator for Bresenham {
     }
 }
 
+impl<T: SignedNum> DoubleEndedIterator for Bresenham<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.x >= self.x_back {
+            None
+        } else {
+            Some(self.advance_back())
+        }
+    }
+}
+
 /// New type over `Bresenham` which include the `end` points when iterated over.
-pub struct BresenhamInclusive(Bresenham);
-impl BresenhamInclusive {
+pub struct BresenhamInclusive<T = isize> {
+    line: Bresenham<T>,
+    // Set once the `end` point has been yielded, from either end of the
+    // iterator, so it is never produced twice.
+    end_emitted: bool,
+}
+impl<T: SignedNum> BresenhamInclusive<T> {
     /// Creates a new iterator. Yields points `start..=end`.
     #[inline]
-    pub fn new(start: Point, end: Point) -> Self {
-        Self(Bresenham::new(start, end))
+    pub fn new(start: (T, T), end: (T, T)) -> Self {
+        Self {
+            line: Bresenham::new(start, end),
+            end_emitted: false,
+        }
+    }
+
+    /// Return the next point without checking if we are past `end`.
+    #[inline]
+    pub fn advance(&mut self) -> (T, T) {
+        self.line.advance()
+    }
+
+    /// Adapts this iterator to yield `(point, next_point)` pairs instead
+    /// of individual points. See [`Steps`].
+    #[inline]
+    pub fn steps(self) -> Steps<Self> {
+        Steps::new(self)
+    }
+}
+impl<T: SignedNum> Iterator for BresenhamInclusive<T> {
+    type Item = (T, T);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.line.x > self.line.x_back || (self.line.x == self.line.x_back && self.end_emitted)
+        {
+            None
+        } else {
+            self.end_emitted |= self.line.x == self.line.x_back;
+            Some(self.line.advance())
+        }
+    }
+}
+
+impl<T: SignedNum> DoubleEndedIterator for BresenhamInclusive<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.line.x > self.line.x_back || (self.line.x == self.line.x_back && self.end_emitted)
+        {
+            None
+        } else if self.end_emitted {
+            Some(self.line.advance_back())
+        } else {
+            self.end_emitted = true;
+            Some(self.line.octant.from_octant0((self.line.x_back, self.line.y_back)))
+        }
+    }
+}
+
+/// Adaptor yielding consecutive point pairs from a line-drawing iterator.
+///
+/// Built with [`Bresenham::steps`] or [`BresenhamInclusive::steps`]. Each
+/// item is a point and its immediate successor along the line, which is
+/// what's needed to draw short segments, compute a per-step direction, or
+/// interpolate a second attribute (color, z-depth) between adjacent
+/// pixels, without the caller having to keep track of the previous point
+/// itself.
+pub struct Steps<I: Iterator> {
+    iter: I,
+    prev: Option<I::Item>,
+}
+
+impl<I: Iterator> Steps<I>
+where
+    I::Item: Copy,
+{
+    #[inline]
+    fn new(mut iter: I) -> Steps<I> {
+        let prev = iter.next();
+        Steps { iter, prev }
+    }
+}
+
+impl<I: Iterator> Iterator for Steps<I>
+where
+    I::Item: Copy,
+{
+    type Item = (I::Item, I::Item);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let prev = self.prev?;
+        let next = self.iter.next()?;
+        self.prev = Some(next);
+        Some((prev, next))
+    }
+}
+
+/// Line-drawing iterator over floating-point endpoints, yielding integer
+/// grid coordinates.
+///
+/// Unlike `Bresenham`, which requires integer `start`/`end` points,
+/// `Midpoint` accepts sub-pixel-accurate endpoints (e.g. physics
+/// positions) and rounds internally, so callers don't have to round
+/// themselves before drawing.
+pub struct Midpoint {
+    x: f64,
+    y: f64,
+    x1: f64,
+    a: f64,
+    b: f64,
+    k: f64,
+    octant: Octant,
+}
+
+impl Midpoint {
+    /// Creates a new iterator. Yields intermediate points between `start`
+    /// and `end`. Does include `start` but not `end`.
+    #[inline]
+    pub fn new(start: (f64, f64), end: (f64, f64)) -> Midpoint {
+        let octant = Octant::from_points(start, end);
+
+        let start = octant.to_octant0(start);
+        let end = octant.to_octant0(end);
+
+        let a = end.1 - start.1;
+        let b = start.0 - end.0;
+        let c = end.0 * start.1 - start.0 * end.1;
+
+        let x = round(start.0);
+        let y = round(start.1);
+        let k = a * (x + 1.0) + b * (y + 0.5) + c;
+
+        Midpoint {
+            x,
+            y,
+            x1: round(end.0),
+            a,
+            b,
+            k,
+            octant,
+        }
     }
 
     /// Return the next point without checking if we are past `end`.
     #[inline]
     pub fn advance(&mut self) -> Point {
-        self.0.advance()
+        let p = (self.x, self.y);
+
+        if self.k >= 0.0 {
+            self.y += 1.0;
+            self.k += self.b;
+        }
+        self.k += self.a;
+        self.x += 1.0;
+
+        let (x, y) = self.octant.from_octant0(p);
+        (x as isize, y as isize)
     }
 }
-impl Iterator for BresenhamInclusive {
+
+impl Iterator for Midpoint {
     type Item = Point;
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        if self.0.x > self.0.x1 {
+        if self.x >= self.x1 {
             None
         } else {
-            Some(self.0.advance())
+            Some(self.advance())
         }
     }
 }
 
+/// Line-drawing iterator that steps along the normalized direction vector
+/// from `start` to `end`, rounding each position to the nearest grid cell.
+///
+/// This is an alternative to the error-term approach `Bresenham` uses: on
+/// some workloads the unit-vector stepping is faster, and it produces a
+/// visually different, more "centered" cell set. Repeated cells (where two
+/// consecutive steps round to the same point) are skipped so every yielded
+/// point is distinct from the last. Does include `start` but not `end`.
+pub struct Vector {
+    x: f64,
+    y: f64,
+    ux: f64,
+    uy: f64,
+    end: Point,
+    len: f64,
+    traveled: f64,
+    last: Option<Point>,
+}
+
+impl Vector {
+    /// Creates a new iterator. Yields intermediate points between `start`
+    /// and `end`. Does include `start` but not `end`.
+    #[inline]
+    pub fn new(start: Point, end: Point) -> Vector {
+        let dx = (end.0 - start.0) as f64;
+        let dy = (end.1 - start.1) as f64;
+        let len = sqrt(dx * dx + dy * dy);
+        let (ux, uy) = if len > 0.0 { (dx / len, dy / len) } else { (0.0, 0.0) };
+
+        Vector {
+            x: start.0 as f64,
+            y: start.1 as f64,
+            ux,
+            uy,
+            end,
+            len,
+            traveled: 0.0,
+            last: None,
+        }
+    }
+}
+
+impl Iterator for Vector {
+    type Item = Point;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        // `traveled < self.len` alone only bounds how many cells we visit;
+        // it doesn't stop us from landing exactly on `end` whenever `len`
+        // isn't an integer, which it rarely is off-axis. So `end` itself
+        // is checked for explicitly and excluded, and the distance check
+        // just keeps this loop from spinning forever (e.g. if float drift
+        // ever made a step miss `end`'s cell outright).
+        while self.traveled < self.len {
+            let cell = (round(self.x) as isize, round(self.y) as isize);
+            self.x += self.ux;
+            self.y += self.uy;
+            self.traveled += 1.0;
+
+            if cell == self.end {
+                self.traveled = self.len;
+                return None;
+            }
+
+            if Some(cell) != self.last {
+                self.last = Some(cell);
+                return Some(cell);
+            }
+        }
+
+        None
+    }
+}
+
+/// Selects the line-walking algorithm used by [`line`].
+pub enum LineAlg {
+    /// Exact integer traversal driven by the Bresenham error term. See
+    /// [`Bresenham`].
+    Bresenham,
+    /// Floating-point unit-vector stepping. See [`Vector`].
+    Vector,
+}
+
+/// Iterator returned by [`line`], wrapping whichever algorithm was chosen
+/// behind a single type.
+pub enum Line {
+    Bresenham(Bresenham),
+    Vector(Vector),
+}
+
+impl Iterator for Line {
+    type Item = Point;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Line::Bresenham(b) => b.next(),
+            Line::Vector(v) => v.next(),
+        }
+    }
+}
+
+/// Draws a line between `start` and `end`, using whichever algorithm `alg`
+/// selects.
+///
+/// This is a convenience entry point for callers who want to pick the
+/// traversal algorithm (or let a caller-supplied setting pick it) without
+/// matching on `LineAlg` themselves at every call site.
+#[inline]
+pub fn line(start: Point, end: Point, alg: LineAlg) -> Line {
+    match alg {
+        LineAlg::Bresenham => Line::Bresenham(Bresenham::new(start, end)),
+        LineAlg::Vector => Line::Vector(Vector::new(start, end)),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -268,4 +637,161 @@ mod tests {
         let res: Vec<_> = bi.collect();
         assert_eq!(res, [(2, 3), (2, 4), (2, 5), (2, 6)]);
     }
+
+    #[test]
+    fn test_midpoint_wp_example() {
+        let mi = Midpoint::new((0.0, 1.0), (6.0, 4.0));
+        let res: Vec<_> = mi.collect();
+        assert_eq!(res, [(0, 1), (1, 2), (2, 2), (3, 3), (4, 3), (5, 4)]);
+    }
+
+    #[test]
+    fn test_midpoint_subpixel() {
+        let mi = Midpoint::new((0.2, 1.4), (6.4, 4.1));
+        let res: Vec<_> = mi.collect();
+        assert_eq!(res, [(0, 1), (1, 2), (2, 2), (3, 3), (4, 3), (5, 3)]);
+    }
+
+    #[test]
+    fn test_midpoint_straight_lines() {
+        let mi = Midpoint::new((2.0, 3.0), (5.0, 3.0));
+        let res: Vec<_> = mi.collect();
+        assert_eq!(res, [(2, 3), (3, 3), (4, 3)]);
+
+        let mi = Midpoint::new((2.0, 3.0), (2.0, 6.0));
+        let res: Vec<_> = mi.collect();
+        assert_eq!(res, [(2, 3), (2, 4), (2, 5)]);
+    }
+
+    #[test]
+    fn test_double_ended() {
+        let bi = Bresenham::new((0, 1), (6, 4));
+        let res: Vec<_> = bi.rev().collect();
+        assert_eq!(res, [(5, 3), (4, 3), (3, 2), (2, 2), (1, 1), (0, 1)]);
+
+        let bi = BresenhamInclusive::new((0, 1), (6, 4));
+        let res: Vec<_> = bi.rev().collect();
+        assert_eq!(
+            res,
+            [(6, 4), (5, 3), (4, 3), (3, 2), (2, 2), (1, 1), (0, 1)]
+        );
+
+        // front and back cursors meeting in the middle
+        let mut bi = Bresenham::new((0, 1), (6, 4));
+        assert_eq!(bi.next(), Some((0, 1)));
+        assert_eq!(bi.next_back(), Some((5, 3)));
+        assert_eq!(bi.next(), Some((1, 1)));
+        assert_eq!(bi.next_back(), Some((4, 3)));
+        assert_eq!(bi.next(), Some((2, 2)));
+        assert_eq!(bi.next_back(), Some((3, 2)));
+        assert_eq!(bi.next(), None);
+        assert_eq!(bi.next_back(), None);
+
+        let mut bi = BresenhamInclusive::new((0, 1), (6, 4));
+        assert_eq!(bi.next_back(), Some((6, 4)));
+        assert_eq!(bi.next(), Some((0, 1)));
+        assert_eq!(bi.next_back(), Some((5, 3)));
+        assert_eq!(bi.next(), Some((1, 1)));
+        assert_eq!(bi.next_back(), Some((4, 3)));
+        assert_eq!(bi.next(), Some((2, 2)));
+        assert_eq!(bi.next_back(), Some((3, 2)));
+        assert_eq!(bi.next(), None);
+        assert_eq!(bi.next_back(), None);
+    }
+
+    #[test]
+    fn test_double_ended_empty() {
+        let bi = Bresenham::new((0, 0), (0, 0));
+        let res: Vec<_> = bi.rev().collect();
+        assert_eq!(res, []);
+
+        let bi = BresenhamInclusive::new((0, 0), (0, 0));
+        let res: Vec<_> = bi.rev().collect();
+        assert_eq!(res, [(0, 0)]);
+    }
+
+    #[test]
+    fn test_steps() {
+        let res: Vec<_> = Bresenham::new((0, 1), (6, 4)).steps().collect();
+        assert_eq!(
+            res,
+            [
+                ((0, 1), (1, 1)),
+                ((1, 1), (2, 2)),
+                ((2, 2), (3, 2)),
+                ((3, 2), (4, 3)),
+                ((4, 3), (5, 3)),
+            ]
+        );
+
+        let res: Vec<_> = BresenhamInclusive::new((0, 1), (6, 4)).steps().collect();
+        assert_eq!(
+            res,
+            [
+                ((0, 1), (1, 1)),
+                ((1, 1), (2, 2)),
+                ((2, 2), (3, 2)),
+                ((3, 2), (4, 3)),
+                ((4, 3), (5, 3)),
+                ((5, 3), (6, 4)),
+            ]
+        );
+
+        let res: Vec<_> = Bresenham::new((0, 0), (0, 0)).steps().collect();
+        assert_eq!(res, []);
+    }
+
+    #[test]
+    fn test_vector_straight_lines() {
+        let vi = Vector::new((2, 3), (5, 3));
+        let res: Vec<_> = vi.collect();
+        assert_eq!(res, [(2, 3), (3, 3), (4, 3)]);
+
+        let vi = Vector::new((2, 3), (2, 6));
+        let res: Vec<_> = vi.collect();
+        assert_eq!(res, [(2, 3), (2, 4), (2, 5)]);
+    }
+
+    #[test]
+    fn test_vector_excludes_end_on_diagonal() {
+        let res: Vec<_> = Vector::new((0, 0), (1, 1)).collect();
+        assert!(!res.contains(&(1, 1)));
+        assert_eq!(res, [(0, 0)]);
+
+        let res: Vec<_> = Vector::new((46, 24), (44, -49)).collect();
+        assert!(!res.contains(&(44, -49)));
+        assert_eq!(res.first(), Some(&(46, 24)));
+    }
+
+    #[test]
+    fn test_vector_empty() {
+        let vi = Vector::new((4, 4), (4, 4));
+        let res: Vec<_> = vi.collect();
+        assert_eq!(res, []);
+    }
+
+    #[test]
+    fn test_vector_no_repeated_cells() {
+        let res: Vec<_> = Vector::new((0, 0), (20, 1)).collect();
+        for pair in res.windows(2) {
+            assert_ne!(pair[0], pair[1]);
+        }
+        assert_eq!(res[0], (0, 0));
+    }
+
+    #[test]
+    fn test_line_picks_algorithm() {
+        let bres: Vec<_> = line((0, 1), (6, 4), LineAlg::Bresenham).collect();
+        assert_eq!(bres, Bresenham::new((0, 1), (6, 4)).collect::<Vec<_>>());
+
+        let vec: Vec<_> = line((2, 3), (5, 3), LineAlg::Vector).collect();
+        assert_eq!(vec, Vector::new((2, 3), (5, 3)).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_generic_i32() {
+        let bi: Bresenham<i32> = Bresenham::new((0, 1), (6, 4));
+        let res: Vec<_> = bi.collect();
+        assert_eq!(res, [(0, 1), (1, 1), (2, 2), (3, 2), (4, 3), (5, 3)]);
+    }
 }